@@ -0,0 +1,18 @@
+use crate::error::DataDogLoggerError;
+use crate::logger::log::DataDogLog;
+#[cfg(feature = "nonblocking")]
+use async_trait::async_trait;
+
+/// Implemented by types that can deliver logs to DataDog in a blocking fashion
+pub trait DataDogClient {
+    /// Sends given logs to DataDog
+    fn send(&mut self, logs: &[DataDogLog]) -> Result<(), DataDogLoggerError>;
+}
+
+/// Implemented by types that can deliver logs to DataDog asynchronously
+#[cfg(feature = "nonblocking")]
+#[async_trait]
+pub trait AsyncDataDogClient {
+    /// Sends given logs to DataDog
+    async fn send_async(&mut self, logs: &[DataDogLog]) -> Result<(), DataDogLoggerError>;
+}
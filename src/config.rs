@@ -0,0 +1,69 @@
+use crate::logger::DataDogLogLevel;
+use std::time::Duration;
+
+/// Configuration of `DataDogLogger`
+#[derive(Debug, Clone)]
+pub struct DataDogConfig {
+    /// Value of `ddsource` attached to every log
+    pub source: String,
+    /// Value of `service` attached to every log
+    pub service: Option<String>,
+    /// Value of `host` attached to every log
+    pub hostname: Option<String>,
+    /// Value of `ddtags` attached to every log
+    pub tags: Option<String>,
+    /// Whether the logger exposes a self log with internal diagnostic messages
+    pub enable_self_log: bool,
+    /// Bound on the capacity of the channel used to send messages to the logger thread/task.
+    ///
+    /// `None` means the channel is unbounded.
+    pub messages_channel_capacity: Option<usize>,
+    /// Logs less severe than this level are dropped before being enqueued, unless
+    /// [`source_min_level`](Self::source_min_level) overrides it.
+    pub min_level: DataDogLogLevel,
+    /// Overrides [`min_level`](Self::min_level) for this logger's own `source`.
+    ///
+    /// A logger only ever emits logs tagged with its own `source`, so this is a per-instance
+    /// override rather than a per-`ddsource` map: it lets one noisy `DataDogLogger` be turned
+    /// down (or up) without touching the application-wide default carried by `min_level`.
+    pub source_min_level: Option<DataDogLogLevel>,
+    /// Upper bound, in approximate serialized bytes, on the batch of logs held in memory while
+    /// waiting to be shipped to DataDog.
+    ///
+    /// When appending a new log would exceed this cap, the oldest buffered logs are dropped
+    /// (FIFO) until it fits again. This keeps memory bounded under a network stall instead of
+    /// growing without limit. A single log larger than the cap is still kept, alone in the batch,
+    /// so the batch can exceed the cap by that one log. A reasonable default is 4 MB
+    /// (`4 * 1024 * 1024`).
+    pub max_buffer_bytes: usize,
+    /// Maximum number of logs sent to DataDog in a single batch.
+    ///
+    /// A batch is flushed as soon as it reaches this size, or when
+    /// [`flush_interval`](Self::flush_interval) elapses, whichever comes first.
+    pub max_batch_size: usize,
+    /// Maximum time a log can sit in the batch before being flushed, regardless of
+    /// [`max_batch_size`](Self::max_batch_size).
+    ///
+    /// `None` disables the time-based flush, so a batch only ships once it fills up.
+    pub flush_interval: Option<Duration>,
+}
+
+impl Default for DataDogConfig {
+    /// Keeps logging everything (no filtering, no time-based flush) while still bounding memory,
+    /// matching the batch size the logger used before it became configurable.
+    fn default() -> Self {
+        DataDogConfig {
+            source: String::new(),
+            service: None,
+            hostname: None,
+            tags: None,
+            enable_self_log: false,
+            messages_channel_capacity: None,
+            min_level: DataDogLogLevel::Trace,
+            source_min_level: None,
+            max_buffer_bytes: 4 * 1024 * 1024,
+            max_batch_size: 50,
+            flush_interval: None,
+        }
+    }
+}
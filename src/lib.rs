@@ -7,6 +7,10 @@
 //! ### full
 //! Enables all features except for `self-log` that needs to be enabled separately.
 //!
+//! ### async-std
+//! Enables [`DataDogLogger::non_blocking_with_async_std`](logger::DataDogLogger::non_blocking_with_async_std),
+//! a convenience constructor that spawns the logger onto the `async-std` runtime.
+//!
 //! ### http
 //! Enables optional HTTP logger.
 //! It is disabled by default not to bring unnecessary dependencies that increase compilation time.
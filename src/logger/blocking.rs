@@ -0,0 +1,184 @@
+use super::log::DataDogLog;
+use super::message::LoggerMessage;
+use super::subscription::{self, Subscribers};
+use crate::client::DataDogClient;
+use flume::{Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+pub fn logger_thread<T>(
+    mut client: T,
+    logs: Receiver<LoggerMessage>,
+    mut selflog: Option<Sender<String>>,
+    max_buffer_bytes: usize,
+    max_batch_size: usize,
+    flush_interval: Option<Duration>,
+    subscribers: Subscribers,
+) where
+    T: DataDogClient,
+{
+    let mut store = Vec::new();
+    let mut store_bytes = 0usize;
+
+    // An absolute deadline, rearmed only when a batch is actually flushed, so the flush
+    // interval measures time since the last send rather than being reset by every incoming log.
+    let mut deadline = flush_interval.map(|interval| Instant::now() + interval);
+
+    loop {
+        let received = match deadline {
+            Some(deadline) => logs.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => logs.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(LoggerMessage::Log(msg)) => {
+                push_bounded(
+                    &mut store,
+                    &mut store_bytes,
+                    msg,
+                    max_buffer_bytes,
+                    &mut selflog,
+                    &subscribers,
+                );
+                if store.len() >= max_batch_size {
+                    send(&mut client, &mut store, &mut selflog);
+                    store_bytes = store.iter().map(DataDogLog::approx_size).sum();
+                    deadline = flush_interval.map(|interval| Instant::now() + interval);
+                }
+            }
+            Ok(LoggerMessage::Flush(ack)) => {
+                if !store.is_empty() {
+                    send(&mut client, &mut store, &mut selflog);
+                    store_bytes = store.iter().map(DataDogLog::approx_size).sum();
+                }
+                ack.try_send(()).unwrap_or_default();
+                deadline = flush_interval.map(|interval| Instant::now() + interval);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if !store.is_empty() {
+                    send(&mut client, &mut store, &mut selflog);
+                    store_bytes = store.iter().map(DataDogLog::approx_size).sum();
+                }
+                deadline = flush_interval.map(|interval| Instant::now() + interval);
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                if !store.is_empty() {
+                    send(&mut client, &mut store, &mut selflog);
+                }
+                break;
+            }
+        }
+    }
+}
+
+/// Appends `msg` to `store`, evicting the oldest buffered logs (FIFO) until the approximate
+/// serialized size of `store` fits within `max_buffer_bytes`.
+///
+/// Dispatches `msg` to subscribers exactly once, here at append time, rather than at send time,
+/// so a batch retried against a failing client does not fan it out more than once.
+fn push_bounded(
+    store: &mut Vec<DataDogLog>,
+    store_bytes: &mut usize,
+    msg: DataDogLog,
+    max_buffer_bytes: usize,
+    selflog: &mut Option<Sender<String>>,
+    subscribers: &Subscribers,
+) {
+    let msg_size = msg.approx_size();
+    let mut dropped = 0usize;
+    while !store.is_empty() && *store_bytes + msg_size > max_buffer_bytes {
+        let evicted = store.remove(0);
+        *store_bytes -= evicted.approx_size();
+        dropped += 1;
+    }
+    *store_bytes += msg_size;
+
+    subscription::dispatch(subscribers, &msg);
+    store.push(msg);
+
+    if dropped > 0 {
+        if let Some(selflog) = selflog {
+            selflog
+                .try_send(format!("dropped {} logs due to buffer pressure", dropped))
+                .unwrap_or_default();
+        }
+    }
+}
+
+fn send<T>(client: &mut T, logs: &mut Vec<DataDogLog>, selflog: &mut Option<Sender<String>>)
+where
+    T: DataDogClient,
+{
+    if let Err(e) = client.send(logs) {
+        if let Some(selflog) = selflog {
+            selflog.try_send(e.to_string()).unwrap_or_default();
+        }
+    } else {
+        logs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn log_of_size(message_len: usize) -> DataDogLog {
+        DataDogLog {
+            message: "a".repeat(message_len),
+            ddtags: None,
+            ddsource: String::new(),
+            host: String::new(),
+            service: String::new(),
+            level: String::new(),
+            trace_id: String::new(),
+            span_id: String::new(),
+        }
+    }
+
+    fn no_subscribers() -> Subscribers {
+        Arc::new(Mutex::new(Vec::new()))
+    }
+
+    #[test]
+    fn push_bounded_keeps_store_under_byte_cap() {
+        let mut store = Vec::new();
+        let mut store_bytes = 0usize;
+        let subscribers = no_subscribers();
+
+        for _ in 0..3 {
+            push_bounded(&mut store, &mut store_bytes, log_of_size(10), 25, &mut None, &subscribers);
+        }
+
+        assert!(store_bytes <= 25);
+    }
+
+    #[test]
+    fn push_bounded_evicts_oldest_first() {
+        let mut store = Vec::new();
+        let mut store_bytes = 0usize;
+        let subscribers = no_subscribers();
+
+        push_bounded(&mut store, &mut store_bytes, log_of_size(1), 10, &mut None, &subscribers);
+        push_bounded(&mut store, &mut store_bytes, log_of_size(2), 10, &mut None, &subscribers);
+        // Exceeds the cap together with the two already buffered, so the size-1 log is evicted first.
+        push_bounded(&mut store, &mut store_bytes, log_of_size(9), 10, &mut None, &subscribers);
+
+        assert_eq!(store.len(), 1);
+        assert_eq!(store[0].message.len(), 9);
+    }
+
+    #[test]
+    fn push_bounded_reports_dropped_count_on_selflog() {
+        let mut store = Vec::new();
+        let mut store_bytes = 0usize;
+        let subscribers = no_subscribers();
+        let (selflog_sender, selflog_receiver) = flume::bounded(10);
+        let mut selflog = Some(selflog_sender);
+
+        push_bounded(&mut store, &mut store_bytes, log_of_size(5), 5, &mut selflog, &subscribers);
+        push_bounded(&mut store, &mut store_bytes, log_of_size(5), 5, &mut selflog, &subscribers);
+
+        let message = selflog_receiver.try_recv().expect("selflog message for the dropped log");
+        assert_eq!(message, "dropped 1 logs due to buffer pressure");
+    }
+}
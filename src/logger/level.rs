@@ -0,0 +1,53 @@
+use std::fmt::{self, Display};
+
+/// Log level understood by DataDog
+///
+/// Ordered from least to most verbose (`Critical` < `Trace`), so it can be compared against a
+/// [`min_level`](crate::config::DataDogConfig::min_level) threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DataDogLogLevel {
+    /// Critical error, often preceding a crash
+    Critical,
+    /// Error
+    Error,
+    /// Warning
+    Warning,
+    /// Informational message
+    Info,
+    /// Debug message
+    Debug,
+    /// Trace message
+    Trace,
+}
+
+impl DataDogLogLevel {
+    /// Parses the DataDog level label produced by [`Display`], the inverse of `to_string`.
+    ///
+    /// Used internally to compare a rendered [`DataDogLog`](super::log::DataDogLog) against a
+    /// subscriber's level threshold.
+    pub(crate) fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "critical" => Some(DataDogLogLevel::Critical),
+            "error" => Some(DataDogLogLevel::Error),
+            "warning" => Some(DataDogLogLevel::Warning),
+            "info" => Some(DataDogLogLevel::Info),
+            "debug" => Some(DataDogLogLevel::Debug),
+            "trace" => Some(DataDogLogLevel::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DataDogLogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            DataDogLogLevel::Critical => "critical",
+            DataDogLogLevel::Error => "error",
+            DataDogLogLevel::Warning => "warning",
+            DataDogLogLevel::Info => "info",
+            DataDogLogLevel::Debug => "debug",
+            DataDogLogLevel::Trace => "trace",
+        };
+        write!(f, "{}", level)
+    }
+}
@@ -22,3 +22,20 @@ pub struct DataDogLog {
     /// The span in which this log was generated
     pub span_id: String,
 }
+
+impl DataDogLog {
+    /// Approximate size, in bytes, of this log once serialized.
+    ///
+    /// Cheap to compute (no actual serialization), used to keep the in-memory batch buffer
+    /// within [`DataDogConfig::max_buffer_bytes`](crate::config::DataDogConfig::max_buffer_bytes).
+    pub(crate) fn approx_size(&self) -> usize {
+        self.message.len()
+            + self.ddtags.as_ref().map(String::len).unwrap_or(0)
+            + self.ddsource.len()
+            + self.host.len()
+            + self.service.len()
+            + self.level.len()
+            + self.trace_id.len()
+            + self.span_id.len()
+    }
+}
@@ -1,24 +1,31 @@
 use super::blocking;
+use super::message::LoggerMessage;
 #[cfg(feature = "nonblocking")]
 use super::nonblocking;
+use super::subscription::Subscribers;
 use super::{level::DataDogLogLevel, log::DataDogLog};
-use crate::{
-    client::{AsyncDataDogClient, DataDogClient},
-    config::DataDogConfig,
-};
+#[cfg(feature = "nonblocking")]
+use crate::client::AsyncDataDogClient;
+use crate::{client::DataDogClient, config::DataDogConfig, error::DataDogLoggerError};
 use flume::{bounded, unbounded, Receiver, Sender};
 #[cfg(feature = "nonblocking")]
 use futures::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::{fmt::Display, ops::Drop, thread};
 
+/// Capacity of the bounded channel handed out by [`DataDogLogger::subscribe`].
+const SUBSCRIBER_CHANNEL_CAPACITY: usize = 1000;
+
 #[derive(Debug)]
 /// Logger that logs directly to DataDog via HTTP(S)
 pub struct DataDogLogger {
     config: DataDogConfig,
-    logsender: Option<Sender<DataDogLog>>,
+    logsender: Option<Sender<LoggerMessage>>,
     selflogrv: Option<Receiver<String>>,
     selflogsd: Option<Sender<String>>,
     logger_handle: Option<thread::JoinHandle<()>>,
+    subscribers: Subscribers,
 }
 
 impl DataDogLogger {
@@ -51,8 +58,22 @@ impl DataDogLogger {
             None => unbounded(),
         };
 
-        let logger_handle =
-            thread::spawn(move || blocking::logger_thread(client, receiver, slsender));
+        let max_buffer_bytes = config.max_buffer_bytes;
+        let max_batch_size = config.max_batch_size;
+        let flush_interval = config.flush_interval;
+        let subscribers: Subscribers = Arc::new(Mutex::new(Vec::new()));
+        let subscribers_clone = Arc::clone(&subscribers);
+        let logger_handle = thread::spawn(move || {
+            blocking::logger_thread(
+                client,
+                receiver,
+                slsender,
+                max_buffer_bytes,
+                max_batch_size,
+                flush_interval,
+                subscribers_clone,
+            )
+        });
 
         DataDogLogger {
             config,
@@ -60,6 +81,7 @@ impl DataDogLogger {
             selflogrv: slreceiver,
             selflogsd: slogsender_clone,
             logger_handle: Some(logger_handle),
+            subscribers,
         }
     }
 
@@ -79,6 +101,22 @@ impl DataDogLogger {
         logger
     }
 
+    /// Creates new non-blocking `DataDogLogger` instance
+    ///
+    /// Internally spawns logger future to the `async-std` runtime.
+    ///
+    /// It is equivalent to calling [`non_blocking_cold`](Self::non_blocking_cold) and spawning it
+    /// onto `async-std`. Thus it is only a convinience function.
+    #[cfg(feature = "async-std")]
+    pub fn non_blocking_with_async_std<T>(client: T, config: DataDogConfig) -> Self
+    where
+        T: AsyncDataDogClient + Send + 'static,
+    {
+        let (logger, future) = Self::non_blocking_cold(client, config);
+        async_std::task::spawn(future);
+        logger
+    }
+
     /// Creates new non-blocking `DataDogLogger` instance
     ///
     /// What it means is that logger requires executor to run. This executor will host a task that will receive messages to log.
@@ -105,7 +143,16 @@ impl DataDogLogger {
             Some(capacity) => bounded(capacity),
             None => unbounded(),
         };
-        let logger_future = nonblocking::logger_future(client, logreceiver, slsender);
+        let subscribers: Subscribers = Arc::new(Mutex::new(Vec::new()));
+        let logger_future = nonblocking::logger_future(
+            client,
+            logreceiver,
+            slsender,
+            config.max_buffer_bytes,
+            config.max_batch_size,
+            config.flush_interval,
+            Arc::clone(&subscribers),
+        );
 
         let logger = DataDogLogger {
             config,
@@ -113,16 +160,39 @@ impl DataDogLogger {
             selflogrv: slreceiver,
             selflogsd: slogsender_clone,
             logger_handle: None,
+            subscribers,
         };
 
         (logger, logger_future)
     }
 
+    /// Subscribes to a live tap of the logs flowing through this logger, e.g. for a local
+    /// debugging view or a "tail" endpoint.
+    ///
+    /// Only logs at `min_level` or above are cloned onto the returned channel. The logger task
+    /// fans each log out once, as it is appended to the batch awaiting shipment, so a subscriber
+    /// also sees logs that are later evicted under buffer pressure or never delivered. Logs are
+    /// dropped on backpressure if the subscriber falls behind, so a slow subscriber never stalls
+    /// delivery to DataDog or to other subscribers. Each subscriber is responsible for
+    /// serializing its own copies.
+    pub fn subscribe(&self, min_level: DataDogLogLevel) -> Receiver<DataDogLog> {
+        let (sender, receiver) = bounded(SUBSCRIBER_CHANNEL_CAPACITY);
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push((min_level, sender));
+        }
+        receiver
+    }
+
     /// Sends log to DataDog thread or task.
     ///
     /// This function does not invoke any IO operation by itself. Instead it sends messages to logger thread or task using channels.
     /// Therefore it is quite lightweight.
     pub fn log<T: Display>(&self, message: T, level: DataDogLogLevel) {
+        let threshold = self.config.source_min_level.unwrap_or(self.config.min_level);
+        if level > threshold {
+            return;
+        }
+
         let log = DataDogLog {
             message: message.to_string(),
             ddtags: self.config.tags.clone(),
@@ -130,10 +200,12 @@ impl DataDogLogger {
             host: self.config.hostname.clone().unwrap_or_default(),
             ddsource: self.config.source.clone(),
             level: level.to_string(),
+            trace_id: String::new(),
+            span_id: String::new(),
         };
 
         if let Some(ref sender) = self.logsender {
-            match sender.try_send(log) {
+            match sender.try_send(LoggerMessage::Log(log)) {
                 Ok(()) => {
                     // nothing
                 }
@@ -145,6 +217,43 @@ impl DataDogLogger {
             }
         }
     }
+
+    /// Flushes everything currently buffered to DataDog, waiting up to `timeout` for the logger
+    /// thread/task to acknowledge it.
+    ///
+    /// Unlike relying on [`Drop`](Self), which joins the logger thread for an unbounded amount of
+    /// time, this gives a deterministic deadline. Callers should invoke `flush` (or
+    /// [`shutdown`](Self::shutdown)) before [`std::process::exit`], since destructors never run
+    /// there and any buffered logs would otherwise be silently discarded.
+    pub fn flush(&self, timeout: Duration) -> Result<(), DataDogLoggerError> {
+        let deadline = Instant::now() + timeout;
+        let (ack_sender, ack_receiver) = bounded(1);
+        let sender = self
+            .logsender
+            .as_ref()
+            .ok_or_else(|| DataDogLoggerError::OtherError("logger already shut down".to_string()))?;
+
+        sender
+            .send_deadline(LoggerMessage::Flush(ack_sender), deadline)
+            .map_err(|e| DataDogLoggerError::OtherError(e.to_string()))?;
+
+        ack_receiver
+            .recv_deadline(deadline)
+            .map_err(|e| DataDogLoggerError::OtherError(e.to_string()))
+    }
+
+    /// Flushes everything currently buffered, then stops the logger thread/task.
+    ///
+    /// This is an explicit, ordered alternative to relying on [`Drop`](Self): it flushes before
+    /// tearing the logger down rather than racing the final batch against the channel closing.
+    /// Like [`flush`](Self::flush), call this (or `flush`) before [`std::process::exit`].
+    pub fn shutdown(mut self) {
+        let _ = self.flush(Duration::from_secs(5));
+        std::mem::drop(self.logsender.take());
+        if let Some(handle) = self.logger_handle.take() {
+            handle.join().unwrap_or_default();
+        }
+    }
 }
 
 impl Drop for DataDogLogger {
@@ -158,3 +267,56 @@ impl Drop for DataDogLogger {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct CollectingClient {
+        sent: Arc<Mutex<Vec<DataDogLog>>>,
+    }
+
+    impl DataDogClient for CollectingClient {
+        fn send(&mut self, logs: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+            self.sent.lock().unwrap().extend_from_slice(logs);
+            Ok(())
+        }
+    }
+
+    fn received_levels(logger: &DataDogLogger, sent: &Arc<Mutex<Vec<DataDogLog>>>) -> Vec<String> {
+        logger.flush(Duration::from_secs(5)).expect("flush to ack");
+        sent.lock().unwrap().iter().map(|log| log.level.clone()).collect()
+    }
+
+    #[test]
+    fn log_drops_messages_below_threshold_and_keeps_messages_at_or_above_it() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let config = DataDogConfig {
+            source_min_level: Some(DataDogLogLevel::Info),
+            ..Default::default()
+        };
+        let logger = DataDogLogger::blocking(CollectingClient { sent: Arc::clone(&sent) }, config);
+
+        logger.log("too verbose", DataDogLogLevel::Debug);
+        logger.log("at threshold", DataDogLogLevel::Info);
+        logger.log("more severe", DataDogLogLevel::Warning);
+
+        let levels = received_levels(&logger, &sent);
+        assert_eq!(levels, vec!["info", "warning"]);
+    }
+
+    #[test]
+    fn flush_acks_once_buffered_logs_are_sent() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let logger = DataDogLogger::blocking(
+            CollectingClient { sent: Arc::clone(&sent) },
+            DataDogConfig::default(),
+        );
+
+        logger.log("hello", DataDogLogLevel::Info);
+        logger.flush(Duration::from_secs(5)).expect("flush to ack");
+
+        assert_eq!(sent.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,14 @@
+use super::log::DataDogLog;
+use flume::Sender;
+
+/// Message sent over the channel that feeds the logger thread/task.
+///
+/// Carrying both logs and control messages on the same channel keeps ordering: a `Flush`
+/// is only acknowledged once every `Log` queued ahead of it has been handed to the batch.
+#[derive(Debug)]
+pub(crate) enum LoggerMessage {
+    /// A log to be batched and shipped to DataDog
+    Log(DataDogLog),
+    /// Ship everything buffered so far, then acknowledge on the given channel
+    Flush(Sender<()>),
+}
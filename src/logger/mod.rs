@@ -0,0 +1,16 @@
+mod blocking;
+mod level;
+/// Log record sent to DataDog
+pub mod log;
+#[allow(clippy::module_inception)]
+mod logger;
+mod message;
+#[cfg(feature = "nonblocking")]
+mod nonblocking;
+#[cfg(feature = "nonblocking")]
+mod sleep;
+mod subscription;
+
+pub use level::DataDogLogLevel;
+pub use log::DataDogLog;
+pub use logger::DataDogLogger;
@@ -1,47 +1,110 @@
 use super::log::DataDogLog;
+use super::message::LoggerMessage;
+use super::sleep::sleep;
+use super::subscription::{self, Subscribers};
 use crate::client::AsyncDataDogClient;
-use flume::{Receiver, Sender, TryRecvError};
-use futures::{Future, StreamExt};
+use flume::{Receiver, Sender};
+use futures::{future, select, FutureExt, StreamExt};
+use std::time::Duration;
 
-pub fn logger_future<T>(
+pub async fn logger_future<T>(
     mut client: T,
-    logs: Receiver<DataDogLog>,
+    logs: Receiver<LoggerMessage>,
     mut selflog: Option<Sender<String>>,
-) -> impl Future<Output = ()>
-where
+    max_buffer_bytes: usize,
+    max_batch_size: usize,
+    flush_interval: Option<Duration>,
+    subscribers: Subscribers,
+) where
     T: AsyncDataDogClient,
 {
-    async move {
-        let mut store = Vec::new();
-        let mut peekable_logs = logs.stream().peekable();
-        loop {
-            match logs.try_recv() {
-                Ok(msg) => {
-                    println!("got message");
-                    if store.len() < 50 {
-                        store.push(msg);
-                        continue;
-                    } else {
-                        store.push(msg);
+    let mut store = Vec::new();
+    let mut store_bytes = 0usize;
+    let mut logs = logs.into_stream().fuse();
+
+    // A single timer that spans loop iterations: it is only rearmed once a batch is
+    // actually flushed, so the flush interval measures time since the last send rather
+    // than being reset by every incoming log.
+    let mut flush_timer = match flush_interval {
+        Some(interval) => sleep(interval).fuse(),
+        None => future::pending().boxed().fuse(),
+    };
+
+    loop {
+        select! {
+            msg = logs.next() => match msg {
+                Some(LoggerMessage::Log(msg)) => {
+                    push_bounded(&mut store, &mut store_bytes, msg, max_buffer_bytes, &mut selflog, &subscribers)
+                        .await;
+                    if store.len() >= max_batch_size {
                         send(&mut client, &mut store, &mut selflog).await;
+                        store_bytes = store.iter().map(DataDogLog::approx_size).sum();
+                        if let Some(interval) = flush_interval {
+                            flush_timer = sleep(interval).fuse();
+                        }
                     }
                 }
-                Err(TryRecvError::Empty) => {
-                    println!("in empty");
+                Some(LoggerMessage::Flush(ack)) => {
                     if !store.is_empty() {
                         send(&mut client, &mut store, &mut selflog).await;
+                        store_bytes = store.iter().map(DataDogLog::approx_size).sum();
+                    }
+                    ack.send_async(()).await.unwrap_or_default();
+                    if let Some(interval) = flush_interval {
+                        flush_timer = sleep(interval).fuse();
                     }
-                    // a trick not to spin endlessly on empty receiver
-                    let _ = peekable_logs.next().await;
                 }
-                Err(TryRecvError::Disconnected) => {
-                    println!("in disconnected");
+                None => {
                     if !store.is_empty() {
                         send(&mut client, &mut store, &mut selflog).await;
                     }
-                    break ();
+                    break;
                 }
-            };
+            },
+            _ = flush_timer => {
+                if !store.is_empty() {
+                    send(&mut client, &mut store, &mut selflog).await;
+                    store_bytes = store.iter().map(DataDogLog::approx_size).sum();
+                }
+                if let Some(interval) = flush_interval {
+                    flush_timer = sleep(interval).fuse();
+                }
+            }
+        }
+    }
+}
+
+/// Appends `msg` to `store`, evicting the oldest buffered logs (FIFO) until the approximate
+/// serialized size of `store` fits within `max_buffer_bytes`.
+///
+/// Dispatches `msg` to subscribers exactly once, here at append time, rather than at send time,
+/// so a batch retried against a failing client does not fan it out more than once.
+async fn push_bounded(
+    store: &mut Vec<DataDogLog>,
+    store_bytes: &mut usize,
+    msg: DataDogLog,
+    max_buffer_bytes: usize,
+    selflog: &mut Option<Sender<String>>,
+    subscribers: &Subscribers,
+) {
+    let msg_size = msg.approx_size();
+    let mut dropped = 0usize;
+    while !store.is_empty() && *store_bytes + msg_size > max_buffer_bytes {
+        let evicted = store.remove(0);
+        *store_bytes -= evicted.approx_size();
+        dropped += 1;
+    }
+    *store_bytes += msg_size;
+
+    subscription::dispatch(subscribers, &msg);
+    store.push(msg);
+
+    if dropped > 0 {
+        if let Some(selflog) = selflog {
+            selflog
+                .send_async(format!("dropped {} logs due to buffer pressure", dropped))
+                .await
+                .unwrap_or_default();
         }
     }
 }
@@ -50,7 +113,7 @@ async fn send<T>(client: &mut T, logs: &mut Vec<DataDogLog>, selflog: &mut Optio
 where
     T: AsyncDataDogClient,
 {
-    if let Err(e) = client.send_async(&logs).await {
+    if let Err(e) = client.send_async(logs).await {
         if let Some(selflog) = selflog {
             selflog.send_async(e.to_string()).await.unwrap_or_default()
         }
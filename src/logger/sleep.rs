@@ -0,0 +1,13 @@
+use futures::future::BoxFuture;
+use futures_timer::Delay;
+use std::time::Duration;
+
+/// Executor-agnostic sleep used for the batching loop's time-based flush.
+///
+/// Backed by `futures-timer`, which runs its own background thread rather than depending on a
+/// specific async runtime, so [`logger_future`](super::nonblocking::logger_future) stays usable
+/// under tokio, async-std, or any other executor passed to
+/// [`non_blocking_cold`](super::DataDogLogger::non_blocking_cold).
+pub(crate) fn sleep(duration: Duration) -> BoxFuture<'static, ()> {
+    Box::pin(Delay::new(duration))
+}
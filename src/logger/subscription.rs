@@ -0,0 +1,35 @@
+use super::{level::DataDogLogLevel, log::DataDogLog};
+use flume::{Sender, TrySendError};
+use std::sync::{Arc, Mutex};
+
+/// Live subscribers fed by the logger thread/task, each paired with the minimum level it wants.
+///
+/// Shared between [`DataDogLogger`](super::DataDogLogger) and the logger thread/task so
+/// `subscribe` can register new subscribers without disturbing the shipping path.
+pub(crate) type Subscribers = Arc<Mutex<Vec<(DataDogLogLevel, Sender<DataDogLog>)>>>;
+
+/// Clones `log` onto the channel of every subscriber whose threshold it meets.
+///
+/// Called once per log, as it is appended to the batch, so it fires exactly once regardless of
+/// how many times the batch is later retried against DataDog. Uses `try_send` so a subscriber
+/// that is slow to drain (e.g. slow at JSON-encoding) is dropped from, not blocking, this call;
+/// it never stalls delivery to DataDog or to other subscribers. Subscribers whose receiver has
+/// been dropped are pruned from the list.
+pub(crate) fn dispatch(subscribers: &Subscribers, log: &DataDogLog) {
+    let mut subscribers = match subscribers.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+    if subscribers.is_empty() {
+        return;
+    }
+
+    let level = DataDogLogLevel::from_label(&log.level);
+    subscribers.retain(|(min_level, sender)| {
+        let qualifies = level.map(|level| level <= *min_level).unwrap_or(true);
+        if !qualifies {
+            return true;
+        }
+        !matches!(sender.try_send(log.clone()), Err(TrySendError::Disconnected(_)))
+    });
+}